@@ -0,0 +1,53 @@
+use std::io::{Read, Seek};
+
+use binread::{BinRead, BinResult, ReadOptions};
+
+/// A hive cell: a signed `i32` size prefix (negative while the cell is
+/// allocated) followed by the cell's actual data.
+///
+/// Most parsed structures in this crate (`nk`, `vk`, `sk`, ...) are stored as
+/// cells; wrapping them in `Cell<T, _>` lets the size prefix be consumed
+/// without every such struct having to repeat the same two fields.
+#[allow(dead_code)]
+pub struct Cell<T, Args>
+where
+    T: BinRead<Args = Args>,
+    Args: Copy + 'static,
+{
+    size: i32,
+    data: T,
+}
+
+impl<T, Args> Cell<T, Args>
+where
+    T: BinRead<Args = Args>,
+    Args: Copy + 'static,
+{
+    /// Returns a reference to the wrapped structure.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Consumes the cell, returning the wrapped structure.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<T, Args> BinRead for Cell<T, Args>
+where
+    T: BinRead<Args = Args>,
+    Args: Copy + 'static,
+{
+    type Args = Args;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args,
+    ) -> BinResult<Self> {
+        let size = i32::read_options(reader, options, ())?;
+        let data = T::read_options(reader, options, args)?;
+        Ok(Self { size, data })
+    }
+}
@@ -0,0 +1,12 @@
+use binread::BinRead;
+
+/// A cell whose data is nothing but a flat `u8` buffer (a `db` data block,
+/// for instance), once the surrounding [`Cell`](crate::Cell) has already
+/// consumed the size prefix.
+#[allow(dead_code)]
+#[derive(BinRead, Debug)]
+#[br(import(count: usize))]
+pub(crate) struct CellWithU8List {
+    #[br(count=count)]
+    pub(crate) data: Vec<u8>,
+}
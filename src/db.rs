@@ -0,0 +1,149 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use binread::{BinReaderExt, BinResult};
+
+use crate::Hive;
+use crate::Offset;
+
+/// Values whose data is larger than this many bytes are not stored inline
+/// (or directly at `data_offset`) but as a `db` "big data" record.
+pub(crate) const MAX_INLINE_VALUE_SIZE: u32 = 16344;
+
+/// Reassembles the payload of a `db` "big data" cell at `offset` into
+/// `data_length` bytes.
+pub(crate) fn read_big_data<B>(
+    hive: &mut Hive<B>,
+    offset: Offset,
+    data_length: u32,
+) -> BinResult<Vec<u8>>
+where
+    B: BinReaderExt,
+{
+    hive.seek(SeekFrom::Start(hive.resolve(offset)))?;
+
+    let mut magic = [0u8; 2];
+    hive.read_exact(&mut magic)?;
+    if &magic != b"db" {
+        return Err(binread::Error::AssertFail {
+            pos: hive.resolve(offset),
+            message: format!("expected a 'db' big-data cell, found {:?}", magic),
+        });
+    }
+
+    let segment_count: u16 = hive.read_le()?;
+    let segment_list_offset: Offset = hive.read_le()?;
+
+    hive.seek(SeekFrom::Start(hive.resolve(segment_list_offset)))?;
+    let segment_offsets: Vec<Offset> = (0..segment_count)
+        .map(|_| hive.read_le())
+        .collect::<BinResult<Vec<Offset>>>()?;
+
+    let mut data = Vec::with_capacity(data_length as usize);
+    for segment_offset in segment_offsets {
+        if data.len() >= data_length as usize {
+            break;
+        }
+
+        hive.seek(SeekFrom::Start(hive.resolve(segment_offset)))?;
+        let remaining = data_length as usize - data.len();
+        let to_read = remaining.min(MAX_INLINE_VALUE_SIZE as usize);
+        let mut block = vec![0u8; to_read];
+        hive.read_exact(&mut block)?;
+        data.extend(block);
+    }
+
+    if data.len() != data_length as usize {
+        return Err(binread::Error::AssertFail {
+            pos: hive.resolve(offset),
+            message: format!(
+                "big-data segments for cell at {:?} only yielded {} of {} expected bytes",
+                offset,
+                data.len(),
+                data_length
+            ),
+        });
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Builds the bytes of a synthetic hive whose first `hbin` (right after
+    /// the 4096-byte base block) is `cells`, so that `Offset(n)` in a test
+    /// lands at byte `n` of `cells`.
+    fn hive_with_cells(cells: &[u8]) -> Hive<Cursor<Vec<u8>>> {
+        let mut bytes = vec![0u8; 4096];
+        bytes.extend_from_slice(cells);
+        Hive::new(Cursor::new(bytes)).unwrap()
+    }
+
+    #[test]
+    fn reassembles_two_segments_and_trims_the_last() {
+        // Every segment but the last holds `MAX_INLINE_VALUE_SIZE` bytes; the
+        // two segments are kept apart so that segment 1 must really be read.
+        let segment_0 = 24u32;
+        let segment_1 = segment_0 + MAX_INLINE_VALUE_SIZE + 8;
+        let first_block: Vec<u8> = (0..MAX_INLINE_VALUE_SIZE).map(|i| i as u8).collect();
+
+        let mut cells = Vec::new();
+        cells.extend_from_slice(b"db");
+        cells.extend_from_slice(&2u16.to_le_bytes());
+        cells.extend_from_slice(&16u32.to_le_bytes()); // segment_list_offset
+
+        while cells.len() < 16 {
+            cells.push(0);
+        }
+        cells.extend_from_slice(&segment_0.to_le_bytes()); // segment 0
+        cells.extend_from_slice(&segment_1.to_le_bytes()); // segment 1
+
+        while cells.len() < segment_0 as usize {
+            cells.push(0);
+        }
+        cells.extend_from_slice(&first_block);
+
+        while cells.len() < segment_1 as usize {
+            cells.push(0xEE);
+        }
+        cells.extend_from_slice(&[9, 10, 0xAA, 0xBB]); // only the first 2 bytes are wanted
+
+        let mut hive = hive_with_cells(&cells);
+        let data = read_big_data(&mut hive, Offset(0), MAX_INLINE_VALUE_SIZE + 2).unwrap();
+        let mut expected = first_block.clone();
+        expected.extend_from_slice(&[9, 10]);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn a_data_length_of_exactly_one_block_needs_no_trimming() {
+        let mut cells = Vec::new();
+        cells.extend_from_slice(b"db");
+        cells.extend_from_slice(&1u16.to_le_bytes());
+        cells.extend_from_slice(&16u32.to_le_bytes());
+
+        while cells.len() < 16 {
+            cells.push(0);
+        }
+        cells.extend_from_slice(&20u32.to_le_bytes());
+
+        while cells.len() < 20 {
+            cells.push(0);
+        }
+        cells.extend_from_slice(&[0xAB; MAX_INLINE_VALUE_SIZE as usize]);
+
+        let mut hive = hive_with_cells(&cells);
+        let data = read_big_data(&mut hive, Offset(0), MAX_INLINE_VALUE_SIZE).unwrap();
+        assert_eq!(data.len(), MAX_INLINE_VALUE_SIZE as usize);
+        assert!(data.iter().all(|b| *b == 0xAB));
+    }
+
+    #[test]
+    fn rejects_a_cell_that_is_not_a_db_record() {
+        let cells = b"nk".to_vec();
+        let mut hive = hive_with_cells(&cells);
+        assert!(read_big_data(&mut hive, Offset(0), 10).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Test-only helpers shared across the crate's unit tests.
+
+#[cfg(test)]
+pub mod tests {
+    /// Accumulates the cells of a test hive's first `hbin`.
+    struct Builder {
+        bytes: Vec<u8>,
+    }
+
+    impl Builder {
+        fn new() -> Self {
+            Self { bytes: Vec::new() }
+        }
+
+        /// Byte offset, relative to the start of the first `hbin` (see
+        /// `Hive::resolve`), of the next cell to be appended.
+        fn offset(&self) -> u32 {
+            self.bytes.len() as u32
+        }
+
+        fn u16(&mut self, v: u16) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn u32(&mut self, v: u32) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn u64(&mut self, v: u64) -> &mut Self {
+            self.bytes.extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        fn bytes(&mut self, v: &[u8]) -> &mut Self {
+            self.bytes.extend_from_slice(v);
+            self
+        }
+
+        /// Appends an `nk` cell with no values and an ASCII (`KEY_COMP_NAME`)
+        /// name, following the exact field order of `nk::KeyNode`.
+        fn nk_cell(
+            &mut self,
+            name: &str,
+            subkey_count: u32,
+            subkeys_list_offset: u32,
+            key_security_offset: u32,
+        ) -> &mut Self {
+            const KEY_COMP_NAME: u16 = 0x0020;
+
+            self.bytes(b"nk")
+                .u16(KEY_COMP_NAME) // flags
+                .u64(0) // timestamp
+                .u32(0) // access_bits
+                .u32(0) // parent
+                .u32(subkey_count)
+                .u32(0) // volatile_subkey_count
+                .u32(subkeys_list_offset)
+                .u32(0) // volatile_subkeys_list_offset
+                .u32(0) // key_values_count
+                .u32(0) // key_values_list_offset
+                .u32(key_security_offset)
+                .u32(u32::MAX) // class_name_offset: none
+                .u32(0) // max_subkey_name
+                .u32(0) // max_subkey_class_name
+                .u32(0) // max_value_name
+                .u32(0) // max_value_data
+                .u32(0) // work_var
+                .u16(name.len() as u16) // key_name_length
+                .u16(0) // class_name_length
+                .bytes(name.as_bytes())
+        }
+
+        /// Appends an `lf` (`FastLeaf`) subkeys list with a single entry.
+        fn lf_cell(&mut self, child_name: &str, child_offset: u32) -> &mut Self {
+            let mut name_hint = [0u8; 4];
+            for (dst, b) in name_hint.iter_mut().zip(child_name.to_uppercase().bytes()) {
+                *dst = b;
+            }
+
+            self.bytes(b"lf")
+                .u16(1) // count
+                .u32(child_offset)
+                .bytes(&name_hint)
+        }
+
+        /// Appends an `sk` cell with an all-empty (ownerless) security
+        /// descriptor, just enough for `KeyNode::security()` to succeed.
+        fn sk_cell(&mut self) -> &mut Self {
+            self.bytes(b"sk")
+                .u16(0) // reserved
+                .u32(0) // prev_sk_offset
+                .u32(0) // next_sk_offset
+                .u32(1) // ref_count
+                .u32(20) // sd_size
+                .bytes(&[0, 0]) // SECURITY_DESCRIPTOR revision, sbz1
+                .u16(0) // control
+                .u32(0) // owner_offset
+                .u32(0) // group_offset
+                .u32(0) // sacl_offset
+                .u32(0) // dacl_offset
+        }
+    }
+
+    /// Builds a small, internally-consistent hive: a 4096-byte base block
+    /// followed by a `"ROOT"` key with one `"child"` subkey (reachable
+    /// through an `lf` subkeys list) and a security descriptor.
+    pub fn testhive_vec() -> Vec<u8> {
+        let mut hbin = Builder::new();
+
+        let root_offset = hbin.offset();
+        assert_eq!(root_offset, 0);
+
+        // Bytes needed by the root `nk` cell before the child/list/sk cells
+        // can be placed after it: 76 fixed bytes + 4 bytes for "ROOT".
+        let root_len = 76 + "ROOT".len() as u32;
+        let lf_offset = root_offset + root_len;
+
+        // `lf` cell with one item: 2 (magic) + 2 (count) + 4 (offset) + 4
+        // (name hint) = 12 bytes.
+        let child_offset = lf_offset + 12;
+
+        // `child` cell: 76 fixed bytes + 5 bytes for "child".
+        let child_len = 76 + "child".len() as u32;
+        let sk_offset = child_offset + child_len;
+
+        hbin.nk_cell("ROOT", 1, lf_offset, sk_offset);
+        hbin.lf_cell("child", child_offset);
+        hbin.nk_cell("child", 0, u32::MAX, u32::MAX);
+        hbin.sk_cell();
+
+        let mut testhive = vec![0u8; 4096];
+        testhive.extend_from_slice(&hbin.bytes);
+        testhive
+    }
+}
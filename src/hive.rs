@@ -0,0 +1,172 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use binread::{BinRead, BinReaderExt, BinResult};
+
+use crate::nk::KeyNode;
+
+/// Hives start their first `hbin` right after the 4096-byte base block; all
+/// cell offsets stored in the hive are relative to that point.
+const FIRST_HBIN_OFFSET: u64 = 4096;
+
+/// Offset of a cell, relative to the start of the first `hbin` (i.e.
+/// relative to byte 4096 of the hive file).
+#[derive(BinRead, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Offset(pub u32);
+
+impl From<Offset> for u64 {
+    fn from(offset: Offset) -> Self {
+        offset.0 as u64
+    }
+}
+
+/// A parsed registry hive, backed by any `Read + Seek` source.
+pub struct Hive<B> {
+    data: B,
+}
+
+impl<B: BinReaderExt> Hive<B> {
+    /// Wraps an existing reader. Call `enum_subkeys()` (which reads the key
+    /// at `Offset(0)`), or `read_structure()` with the root key's offset, to
+    /// start navigating the tree.
+    pub fn new(data: B) -> BinResult<Self> {
+        Ok(Self { data })
+    }
+
+    /// Reads and parses the structure `T` stored at `offset`.
+    pub fn read_structure<T>(&mut self, offset: Offset) -> BinResult<T>
+    where
+        T: BinRead<Args = ()>,
+    {
+        self.data.seek(SeekFrom::Start(self.resolve(offset)))?;
+        self.data.read_le()
+    }
+
+    /// Turns a cell `offset` into an absolute stream position.
+    pub(crate) fn resolve(&self, offset: Offset) -> u64 {
+        FIRST_HBIN_OFFSET + u64::from(offset)
+    }
+
+    /// Parses the root `KeyNode` and invokes `cb` with it.
+    pub fn enum_subkeys<F>(&mut self, cb: F) -> BinResult<()>
+    where
+        F: Fn(&mut Self, &KeyNode) -> BinResult<()>,
+    {
+        let root: KeyNode = self.read_structure(Offset(0))?;
+        cb(self, &root)
+    }
+}
+
+impl<B: Read + Seek> Read for Hive<B> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.data.read(buf)
+    }
+}
+
+impl<B: Read + Seek> Seek for Hive<B> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.data.seek(pos)
+    }
+}
+
+#[cfg(feature = "mmap")]
+mod mmap_backend {
+    use std::fs::File;
+    use std::io::{self, Read, Seek, SeekFrom};
+    use std::path::Path;
+
+    use memmap2::Mmap;
+
+    /// A read-only `Read + Seek` view over a memory-mapped hive file.
+    pub struct MmapBackend {
+        mmap: Mmap,
+        position: u64,
+    }
+
+    impl MmapBackend {
+        pub(crate) fn from_mmap(mmap: Mmap) -> Self {
+            Self { mmap, position: 0 }
+        }
+
+        pub(crate) fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+            let file = File::open(path)?;
+            let mmap = unsafe { Mmap::map(&file)? };
+            Ok(Self::from_mmap(mmap))
+        }
+    }
+
+    impl Read for MmapBackend {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.position >= self.mmap.len() as u64 {
+                return Ok(0);
+            }
+
+            let available = &self.mmap[self.position as usize..];
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            self.position += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl Seek for MmapBackend {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            let new_position = match pos {
+                SeekFrom::Start(offset) => offset as i64,
+                SeekFrom::End(offset) => self.mmap.len() as i64 + offset,
+                SeekFrom::Current(offset) => self.position as i64 + offset,
+            };
+
+            if new_position < 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "attempted to seek before the start of the mapped hive",
+                ));
+            }
+
+            self.position = new_position as u64;
+            Ok(self.position)
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use mmap_backend::MmapBackend;
+
+#[cfg(feature = "mmap")]
+impl Hive<mmap_backend::MmapBackend> {
+    /// Opens and memory-maps the hive file at `path`.
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> BinResult<Self> {
+        let backend = mmap_backend::MmapBackend::open(path).map_err(binread::Error::Io)?;
+        Self::new(backend)
+    }
+
+    /// Like [`Hive::from_path`], but takes an already-open file.
+    pub fn from_file(file: std::fs::File) -> BinResult<Self> {
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(binread::Error::Io)?;
+        Self::new(mmap_backend::MmapBackend::from_mmap(mmap))
+    }
+}
+
+#[cfg(all(test, feature = "mmap"))]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn from_path_parses_the_same_root_as_a_cursor() {
+        let testhive = crate::helpers::tests::testhive_vec();
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("nt-hive2-mmap-test-{}.hive", std::process::id()));
+        std::fs::File::create(&path).unwrap().write_all(&testhive).unwrap();
+
+        let mut hive = Hive::from_path(&path).unwrap();
+        let result = hive.enum_subkeys(|_hive, k| {
+            assert_eq!(k.name(), "ROOT");
+            Ok(())
+        });
+
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok());
+    }
+}
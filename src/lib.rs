@@ -12,6 +12,8 @@ mod vk;
 mod db;
 mod subkeys_list;
 mod cell_with_u8_list;
+mod sk;
 
-pub use nk::{KeyNode, SubPath};
-pub use vk::{KeyValue, RegistryValue};
\ No newline at end of file
+pub use nk::{KeyNode, SubPath, Walk};
+pub use vk::{KeyValue, RegistryValue};
+pub use sk::{SecurityDescriptor, Acl, Ace, Sid};
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::cell::Ref;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
@@ -11,6 +12,7 @@ use crate::subkeys_list::*;
 use crate::Offset;
 use crate::vk::KeyValueList;
 use crate::vk::KeyValue;
+use crate::sk::{SecurityDescriptor, SecurityKey};
 use binread::BinResult;
 use binread::FilePtr32;
 use binread::ReadOptions;
@@ -53,10 +55,8 @@ pub struct KeyNode {
     #[br(temp)]
     key_values_list_offset: u32,
 
-    #[br(temp)]
     key_security_offset: Offset,
-    
-    #[br(temp)]
+
     class_name_offset: Offset,
 
     #[br(temp)]
@@ -77,7 +77,6 @@ pub struct KeyNode {
     #[br(temp)]
     key_name_length: u16,
 
-    #[br(temp)]
     class_name_length: u16,
 
     #[br(   parse_with=parse_string,
@@ -91,7 +90,13 @@ pub struct KeyNode {
     values: Vec<KeyValue>,
 
     #[br(default)]
-    subkeys: Rc<RefCell<Vec<Rc<RefCell<Self>>>>>
+    subkeys: Rc<RefCell<Vec<Rc<RefCell<Self>>>>>,
+
+    /// Nodes resolved through `subkey_fast_path()` before `subkeys` itself
+    /// was ever populated, keyed by their cell offset so `read_subkeys()`
+    /// can later reuse the same instance instead of parsing it again.
+    #[br(default)]
+    fast_subkeys: Rc<RefCell<HashMap<u32, Rc<RefCell<Self>>>>>
 }
 
 fn parse_node_flags<R: Read + Seek>(reader: &mut R, _ro: &ReadOptions, _: ())
@@ -126,6 +131,12 @@ bitflags! {
     }
 }
 
+/// Compares two key names the way Windows does: case-insensitively, with
+/// Unicode-aware case folding rather than a plain ASCII comparison.
+fn names_match(a: &str, b: &str) -> bool {
+    a.to_uppercase() == b.to_uppercase()
+}
+
 impl KeyNode
 {
     /// Returns the name of this Key Node.
@@ -141,7 +152,7 @@ impl KeyNode
         self.subkey_count
     }
 
-    pub fn subkeys<B>(&self, hive: &mut Hive<B>) -> BinResult<Ref<Vec<Rc<RefCell<Self>>>>> where B: BinReaderExt {
+    pub fn subkeys<B>(&self, hive: &mut Hive<B>) -> BinResult<Ref<'_, Vec<Rc<RefCell<Self>>>>> where B: BinReaderExt {
         if self.subkeys.borrow().is_empty() && self.subkey_count() > 0 {
             let sk = self.read_subkeys(hive)?;
             *self.subkeys.borrow_mut() = sk;
@@ -174,8 +185,7 @@ impl KeyNode
                 assert!(!subsubkeys_list.is_index_root());
 
                 let subkeys: BinResult<Vec<_>> = subsubkeys_list.into_offsets().map(|o2| {
-                    let nk: KeyNode = hive.read_structure(o2)?;
-                    Ok(Rc::new(RefCell::new(nk)))
+                    self.read_subkey(o2, hive)
                 }).collect();
                 subkeys
             }).collect();
@@ -187,16 +197,25 @@ impl KeyNode
         } else {
             log::debug!("reading single subkey list");
             let subkeys: BinResult<Vec<_>> = subkeys_list.into_offsets().map(|offset| {
-                let nk: KeyNode = hive.read_structure(offset)?;
-                Ok(Rc::new(RefCell::new(nk)))
+                self.read_subkey(offset, hive)
             }).collect();
             subkeys
         }
     }
+
+    /// Parses the `KeyNode` at `offset`, reusing the `Rc` already cached in
+    /// `fast_subkeys` if `subkey_fast_path()` resolved this same cell before.
+    fn read_subkey<B>(&self, offset: Offset, hive: &mut Hive<B>) -> BinResult<Rc<RefCell<Self>>> where B: BinReaderExt {
+        if let Some(cached) = self.fast_subkeys.borrow().get(&offset.0) {
+            return Ok(Rc::clone(cached));
+        }
+
+        let nk: KeyNode = hive.read_structure(offset)?;
+        Ok(Rc::new(RefCell::new(nk)))
+    }
     
 
     fn subpath_parts<B>(&self, mut path_parts: Vec<&str>, hive: &mut Hive<B>) -> BinResult<Option<Rc<RefCell<Self>>>> where B: BinReaderExt {
-        eprintln!("subpath_parts({:?}): BEGIN", path_parts);
         if let Some(first) = path_parts.pop() {
             if let Some(top) = self.subkey(first, hive)? {
                 return if path_parts.is_empty() {
@@ -210,17 +229,137 @@ impl KeyNode
     }
 
     pub fn subkey<B>(&self, name: &str, hive: &mut Hive<B>) -> BinResult<Option<Rc<RefCell<Self>>>> where B: BinReaderExt {
+        if self.subkeys.borrow().is_empty() {
+            if let Some(found) = self.subkey_fast_path(name, hive)? {
+                return Ok(Some(found));
+            }
+        }
+
         let subkey = self.subkeys(hive)?
             .iter()
-            .find(|s|s.borrow().name() == name)
-            .map(|kn| Rc::clone(kn));
+            .find(|s| names_match(s.borrow().name(), name))
+            .map(Rc::clone);
         Ok(subkey)
     }
 
+    /// Tries to resolve `name` directly from the on-disk subkey list without
+    /// parsing every sibling `KeyNode` first. `HashLeaf` (`lh`) and
+    /// `FastLeaf` (`lf`) lists carry enough shortcut data (a name hash or a
+    /// four-character name hint) per entry to seek straight to the matching
+    /// child; `IndexLeaf` and `IndexRoot` lists carry no such hint and fall
+    /// back to the regular full scan done by `subkey()`. A hit is recorded
+    /// in `fast_subkeys` so that a later full `subkeys()` load reuses the
+    /// same `Rc` instead of parsing the same cell a second time.
+    fn subkey_fast_path<B>(&self, name: &str, hive: &mut Hive<B>) -> BinResult<Option<Rc<RefCell<Self>>>> where B: BinReaderExt {
+        let offset = self.subkeys_list_offset;
+        if offset.0 == u32::MAX {
+            return Ok(None);
+        }
+
+        let subkeys_list: SubKeysList = hive.read_structure(offset)?;
+
+        let candidate = match &subkeys_list {
+            SubKeysList::HashLeaf { .. } => subkeys_list.find_by_hash(hash_name(name)),
+            SubKeysList::FastLeaf { .. } => subkeys_list.find_by_prefix(name_prefix(name)),
+            SubKeysList::IndexLeaf { .. } | SubKeysList::IndexRoot { .. } => None,
+        };
+
+        let subkey_offset = match candidate {
+            None => return Ok(None),
+            Some(subkey_offset) => subkey_offset,
+        };
+
+        if let Some(cached) = self.fast_subkeys.borrow().get(&subkey_offset.0) {
+            return Ok(Some(Rc::clone(cached)));
+        }
+
+        let nk: KeyNode = hive.read_structure(subkey_offset)?;
+        if !names_match(nk.name(), name) {
+            return Ok(None);
+        }
+
+        let nk = Rc::new(RefCell::new(nk));
+        self.fast_subkeys.borrow_mut().insert(subkey_offset.0, Rc::clone(&nk));
+        Ok(Some(nk))
+    }
+
 
     pub fn values(&self) -> &Vec<KeyValue> {
         &self.values
     }
+
+    /// Returns this key's class name, if it has one.
+    pub fn class_name<B>(&self, hive: &mut Hive<B>) -> BinResult<Option<String>> where B: BinReaderExt {
+        if self.class_name_offset.0 == u32::MAX || self.class_name_length == 0 {
+            return Ok(None);
+        }
+
+        hive.seek(SeekFrom::Start(hive.resolve(self.class_name_offset)))?;
+        let units: Vec<u16> = (0..self.class_name_length / 2)
+            .map(|_| hive.read_le())
+            .collect::<BinResult<Vec<u16>>>()?;
+        Ok(Some(String::from_utf16_lossy(&units)))
+    }
+
+    /// Decodes this key's security descriptor, if it has one.
+    pub fn security<B>(&self, hive: &mut Hive<B>) -> BinResult<Option<SecurityDescriptor>> where B: BinReaderExt {
+        if self.key_security_offset.0 == u32::MAX {
+            return Ok(None);
+        }
+
+        let sk: SecurityKey = hive.read_structure(self.key_security_offset)?;
+        Ok(Some(sk.into_security_descriptor()))
+    }
+
+    /// Depth-first-walks this key's subtree, yielding each descendant with
+    /// its path relative to `self`.
+    pub fn walk<'a, B>(&self, hive: &'a mut Hive<B>) -> Walk<'a, B> where B: BinReaderExt {
+        let (stack, error) = match self.subkeys(hive) {
+            Ok(children) => (
+                children
+                    .iter()
+                    .rev()
+                    .map(|child| (child.borrow().name().to_string(), Rc::clone(child)))
+                    .collect(),
+                None,
+            ),
+            Err(why) => (Vec::new(), Some(why)),
+        };
+
+        Walk { hive, stack, error }
+    }
+}
+
+/// Lazy, depth-first iterator over a `KeyNode`'s descendants. See
+/// [`KeyNode::walk`].
+pub struct Walk<'a, B> where B: BinReaderExt {
+    hive: &'a mut Hive<B>,
+    stack: Vec<(String, Rc<RefCell<KeyNode>>)>,
+    error: Option<binread::Error>,
+}
+
+impl<'a, B> Iterator for Walk<'a, B> where B: BinReaderExt {
+    type Item = BinResult<(String, Rc<RefCell<KeyNode>>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(why) = self.error.take() {
+            return Some(Err(why));
+        }
+
+        let (path, node) = self.stack.pop()?;
+
+        match node.borrow().subkeys(self.hive) {
+            Ok(children) => {
+                for child in children.iter().rev() {
+                    let child_path = format!("{}\\{}", path, child.borrow().name());
+                    self.stack.push((child_path, Rc::clone(child)));
+                }
+            }
+            Err(why) => return Some(Err(why)),
+        }
+
+        Some(Ok((path, node)))
+    }
 }
 
 pub trait SubPath<T> {
@@ -243,7 +382,7 @@ impl SubPath<&String> for KeyNode {
 
 impl SubPath<&Vec<&str>> for KeyNode {
     fn subpath<B>(&self, path: &Vec<&str>, hive: &mut Hive<B>) -> BinResult<Option<Rc<RefCell<Self>>>> where B: BinReaderExt {
-        let path_parts: Vec<_> = path.iter().rev().map(|s| *s).collect();
+        let path_parts: Vec<_> = path.iter().rev().copied().collect();
         self.subpath_parts(path_parts, hive)
     }
 }
@@ -256,10 +395,12 @@ impl SubPath<&Vec<String>> for KeyNode {
 }
 
 
+type KeyValuesListPtr<'a> = Option<&'a FilePtr32<Cell<KeyValueList, (usize,)>>>;
+
 fn read_values<R: Read + Seek>(
     reader: &mut R,
     _ro: &ReadOptions,
-    args: (Option<&FilePtr32<Cell<KeyValueList, (usize,)>>>, ),
+    args: (KeyValuesListPtr<'_>,),
 ) -> BinResult<Vec<KeyValue>> {
     Ok(match args.0 {
         None => Vec::new(),
@@ -267,7 +408,7 @@ fn read_values<R: Read + Seek>(
             None => Vec::new(),
             Some(kv_list_cell) => {
                 let kv_list: &KeyValueList = kv_list_cell.data();
-                let mut result = Vec::with_capacity(kv_list.key_value_offsets.len() as usize);
+                let mut result = Vec::with_capacity(kv_list.key_value_offsets.len());
                 for offset in kv_list.key_value_offsets.iter() {
                     reader.seek(SeekFrom::Start(offset.0.into()))?;
                     let vk: Cell<KeyValue, ()> = reader.read_le().unwrap();
@@ -288,6 +429,7 @@ impl From<Cell<KeyNode, ()>> for KeyNode {
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use binread::BinResult;
     use std::io;
 
     #[test]
@@ -304,5 +446,81 @@ mod tests {
             Ok(())
         }).is_ok());
     }
+
+    #[test]
+    fn subkey_lookup_is_case_insensitive() {
+        let testhive = crate::helpers::tests::testhive_vec();
+        let mut hive = Hive::new(io::Cursor::new(testhive)).unwrap();
+        assert!(hive.enum_subkeys(|hive, k: &KeyNode| {
+            let first_child = k.subkeys(hive).unwrap()
+                .first().expect("fixture hive has no subkeys")
+                .borrow().name().to_string();
+
+            assert!(k.subkey(&first_child.to_uppercase(), hive).unwrap().is_some());
+            assert!(k.subkey(&first_child.to_lowercase(), hive).unwrap().is_some());
+            Ok(())
+        }).is_ok());
+    }
+
+    #[test]
+    fn subkey_fast_path_hits_and_falls_back_to_none_on_miss() {
+        let testhive = crate::helpers::tests::testhive_vec();
+        let mut hive = Hive::new(io::Cursor::new(testhive)).unwrap();
+        assert!(hive.enum_subkeys(|hive, k: &KeyNode| {
+            let first_child = k.subkeys(hive).unwrap()
+                .first().expect("fixture hive has no subkeys")
+                .borrow().name().to_string();
+
+            // Force `subkeys` back to "not yet loaded" so `subkey()` has to
+            // go through `subkey_fast_path()` rather than the full scan.
+            k.subkeys.borrow_mut().clear();
+            assert!(k.fast_subkeys.borrow().is_empty());
+
+            assert!(k.subkey(&first_child, hive).unwrap().is_some());
+
+            // The hit must have gone through the fast path: it populates
+            // `fast_subkeys` without ever loading the full `subkeys` cache.
+            assert!(k.subkeys.borrow().is_empty());
+            assert!(!k.fast_subkeys.borrow().is_empty());
+
+            // No such child: the hash/prefix fast path must not mistake
+            // this for a match and has to fall back to reporting `None`.
+            assert!(k.subkey("this subkey does not exist", hive).unwrap().is_none());
+            Ok(())
+        }).is_ok());
+    }
+
+    #[test]
+    fn class_name_and_security_are_queryable() {
+        let testhive = crate::helpers::tests::testhive_vec();
+        let mut hive = Hive::new(io::Cursor::new(testhive)).unwrap();
+        assert!(hive.enum_subkeys(|hive, k: &KeyNode| {
+            assert!(k.class_name(hive).is_ok());
+
+            let security = k.security(hive).unwrap();
+            assert!(security.is_some(), "root key should reference an sk cell");
+            Ok(())
+        }).is_ok());
+    }
+
+    #[test]
+    fn walk_visits_every_direct_child_with_its_path() {
+        let testhive = crate::helpers::tests::testhive_vec();
+        let mut hive = Hive::new(io::Cursor::new(testhive)).unwrap();
+        assert!(hive.enum_subkeys(|hive, k: &KeyNode| {
+            let direct_children: Vec<_> = k.subkeys(hive).unwrap()
+                .iter()
+                .map(|c| c.borrow().name().to_string())
+                .collect();
+
+            let walked: Vec<_> = k.walk(hive).collect::<BinResult<Vec<_>>>().unwrap();
+
+            for name in &direct_children {
+                assert!(walked.iter().any(|(path, _)| path == name));
+            }
+            assert!(walked.len() >= direct_children.len());
+            Ok(())
+        }).is_ok());
+    }
 }
 
@@ -0,0 +1,235 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use binread::BinReaderExt;
+use binread::BinResult;
+use binread::derive_binread;
+
+use crate::Offset;
+
+/// A Windows security identifier (`S-1-5-...`), as stored self-relative
+/// inside a `SECURITY_DESCRIPTOR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sid {
+    revision: u8,
+    identifier_authority: u64,
+    sub_authorities: Vec<u32>,
+}
+
+impl Sid {
+    fn parse<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        let revision: u8 = reader.read_le()?;
+        let sub_authority_count: u8 = reader.read_le()?;
+
+        let mut authority_bytes = [0u8; 6];
+        reader.read_exact(&mut authority_bytes)?;
+        let identifier_authority = authority_bytes
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+
+        let sub_authorities = (0..sub_authority_count)
+            .map(|_| reader.read_le())
+            .collect::<BinResult<Vec<u32>>>()?;
+
+        Ok(Self {
+            revision,
+            identifier_authority,
+            sub_authorities,
+        })
+    }
+}
+
+impl std::fmt::Display for Sid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "S-{}-{}", self.revision, self.identifier_authority)?;
+        for sub_authority in &self.sub_authorities {
+            write!(f, "-{}", sub_authority)?;
+        }
+        Ok(())
+    }
+}
+
+/// One access control entry of a DACL/SACL.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct Ace {
+    ace_type: u8,
+    ace_flags: u8,
+    access_mask: u32,
+    sid: Sid,
+}
+
+impl Ace {
+    pub fn ace_type(&self) -> u8 {
+        self.ace_type
+    }
+
+    pub fn access_mask(&self) -> u32 {
+        self.access_mask
+    }
+
+    pub fn sid(&self) -> &Sid {
+        &self.sid
+    }
+
+    fn parse<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        let start = reader.stream_position()?;
+        let ace_type: u8 = reader.read_le()?;
+        let ace_flags: u8 = reader.read_le()?;
+        let ace_size: u16 = reader.read_le()?;
+        let access_mask: u32 = reader.read_le()?;
+        let sid = Sid::parse(reader)?;
+        reader.seek(SeekFrom::Start(start + ace_size as u64))?;
+
+        Ok(Self {
+            ace_type,
+            ace_flags,
+            access_mask,
+            sid,
+        })
+    }
+}
+
+/// A discretionary or system access control list: an ordered list of `Ace`s.
+#[derive(Debug, Clone)]
+pub struct Acl {
+    aces: Vec<Ace>,
+}
+
+impl Acl {
+    pub fn aces(&self) -> &[Ace] {
+        &self.aces
+    }
+
+    fn parse<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        let start = reader.stream_position()?;
+        let _revision: u8 = reader.read_le()?;
+        let _sbz1: u8 = reader.read_le()?;
+        let _acl_size: u16 = reader.read_le()?;
+        let ace_count: u16 = reader.read_le()?;
+        let _sbz2: u16 = reader.read_le()?;
+        let _ = start;
+
+        let aces = (0..ace_count)
+            .map(|_| Ace::parse(reader))
+            .collect::<BinResult<Vec<Ace>>>()?;
+
+        Ok(Self { aces })
+    }
+}
+
+/// The owner/group SIDs and DACL/SACL of a key, decoded from the
+/// self-relative `SECURITY_DESCRIPTOR` stored in an `sk` cell.
+#[derive(Debug, Clone)]
+pub struct SecurityDescriptor {
+    owner: Option<Sid>,
+    group: Option<Sid>,
+    dacl: Option<Acl>,
+    sacl: Option<Acl>,
+}
+
+impl SecurityDescriptor {
+    pub fn owner(&self) -> Option<&Sid> {
+        self.owner.as_ref()
+    }
+
+    pub fn group(&self) -> Option<&Sid> {
+        self.group.as_ref()
+    }
+
+    pub fn dacl(&self) -> Option<&Acl> {
+        self.dacl.as_ref()
+    }
+
+    pub fn sacl(&self) -> Option<&Acl> {
+        self.sacl.as_ref()
+    }
+
+    /// Parses a self-relative `SECURITY_DESCRIPTOR` starting at the current
+    /// position of `reader`. `base` is the stream offset of its first byte,
+    /// since the owner/group/sacl/dacl fields are offsets relative to it.
+    pub(crate) fn parse<R: Read + Seek>(reader: &mut R, base: u64) -> BinResult<Self> {
+        let _revision: u8 = reader.read_le()?;
+        let _sbz1: u8 = reader.read_le()?;
+        let _control: u16 = reader.read_le()?;
+        let owner_offset: u32 = reader.read_le()?;
+        let group_offset: u32 = reader.read_le()?;
+        let sacl_offset: u32 = reader.read_le()?;
+        let dacl_offset: u32 = reader.read_le()?;
+
+        let owner = if owner_offset == 0 {
+            None
+        } else {
+            reader.seek(SeekFrom::Start(base + owner_offset as u64))?;
+            Some(Sid::parse(reader)?)
+        };
+
+        let group = if group_offset == 0 {
+            None
+        } else {
+            reader.seek(SeekFrom::Start(base + group_offset as u64))?;
+            Some(Sid::parse(reader)?)
+        };
+
+        let sacl = if sacl_offset == 0 {
+            None
+        } else {
+            reader.seek(SeekFrom::Start(base + sacl_offset as u64))?;
+            Some(Acl::parse(reader)?)
+        };
+
+        let dacl = if dacl_offset == 0 {
+            None
+        } else {
+            reader.seek(SeekFrom::Start(base + dacl_offset as u64))?;
+            Some(Acl::parse(reader)?)
+        };
+
+        Ok(Self {
+            owner,
+            group,
+            dacl,
+            sacl,
+        })
+    }
+}
+
+/// An `sk` cell: the security descriptor shared by (potentially several)
+/// `KeyNode`s, stored in a small ring of `sk` records.
+#[allow(dead_code)]
+#[derive_binread]
+#[br(magic = b"sk")]
+pub(crate) struct SecurityKey {
+    #[br(temp)]
+    reserved: u16,
+
+    #[br(temp)]
+    prev_sk_offset: Offset,
+
+    #[br(temp)]
+    next_sk_offset: Offset,
+
+    #[br(temp)]
+    ref_count: u32,
+
+    #[br(temp)]
+    sd_size: u32,
+
+    #[br(parse_with=parse_security_descriptor, args(sd_size as u64))]
+    security_descriptor: SecurityDescriptor,
+}
+
+impl SecurityKey {
+    pub(crate) fn into_security_descriptor(self) -> SecurityDescriptor {
+        self.security_descriptor
+    }
+}
+
+fn parse_security_descriptor<R: Read + Seek>(
+    reader: &mut R,
+    _ro: &binread::ReadOptions,
+    args: (u64,),
+) -> BinResult<SecurityDescriptor> {
+    let _sd_size = args.0;
+    let base = reader.stream_position()?;
+    SecurityDescriptor::parse(reader, base)
+}
@@ -0,0 +1,118 @@
+use binread::derive_binread;
+use binread::BinRead;
+
+use crate::Offset;
+
+#[derive(BinRead, Debug)]
+pub(crate) struct HashLeafItem {
+    pub subkey_offset: Offset,
+    pub name_hash: u32,
+}
+
+#[derive(BinRead, Debug)]
+pub(crate) struct FastLeafItem {
+    pub subkey_offset: Offset,
+    pub name_hint: [u8; 4],
+}
+
+#[derive(BinRead, Debug)]
+pub(crate) struct IndexLeafItem {
+    pub subkey_offset: Offset,
+}
+
+#[derive(BinRead, Debug)]
+pub(crate) struct IndexRootItem {
+    pub subkeys_list_offset: Offset,
+}
+
+#[allow(dead_code)]
+#[derive_binread]
+#[derive(Debug)]
+pub(crate) enum SubKeysList {
+    #[br(magic = b"lh")]
+    HashLeaf {
+        count: u16,
+        #[br(count=count)]
+        items: Vec<HashLeafItem>,
+    },
+
+    #[br(magic = b"lf")]
+    FastLeaf {
+        count: u16,
+        #[br(count=count)]
+        items: Vec<FastLeafItem>,
+    },
+
+    #[br(magic = b"li")]
+    IndexLeaf {
+        count: u16,
+        #[br(count=count)]
+        items: Vec<IndexLeafItem>,
+    },
+
+    #[br(magic = b"ri")]
+    IndexRoot {
+        count: u16,
+        #[br(count=count)]
+        items: Vec<IndexRootItem>,
+    },
+}
+
+impl SubKeysList {
+    pub(crate) fn is_index_root(&self) -> bool {
+        matches!(self, Self::IndexRoot { .. })
+    }
+
+    pub(crate) fn into_offsets(self) -> Box<dyn Iterator<Item = Offset>> {
+        match self {
+            Self::HashLeaf { items, .. } => Box::new(items.into_iter().map(|i| i.subkey_offset)),
+            Self::FastLeaf { items, .. } => Box::new(items.into_iter().map(|i| i.subkey_offset)),
+            Self::IndexLeaf { items, .. } => Box::new(items.into_iter().map(|i| i.subkey_offset)),
+            Self::IndexRoot { items, .. } => {
+                Box::new(items.into_iter().map(|i| i.subkeys_list_offset))
+            }
+        }
+    }
+
+    /// Looks up the subkey offset whose stored `lh` name hash matches `hash`,
+    /// without having to parse any of the sibling `KeyNode`s.
+    pub(crate) fn find_by_hash(&self, hash: u32) -> Option<Offset> {
+        match self {
+            Self::HashLeaf { items, .. } => items
+                .iter()
+                .find(|i| i.name_hash == hash)
+                .map(|i| i.subkey_offset),
+            _ => None,
+        }
+    }
+
+    /// Looks up the subkey offset whose stored `lf` four-character name hint
+    /// matches `prefix`, without having to parse any of the sibling `KeyNode`s.
+    pub(crate) fn find_by_prefix(&self, prefix: [u8; 4]) -> Option<Offset> {
+        match self {
+            Self::FastLeaf { items, .. } => items
+                .iter()
+                .find(|i| i.name_hint == prefix)
+                .map(|i| i.subkey_offset),
+            _ => None,
+        }
+    }
+}
+
+/// Computes the Windows `lh` hash of a key name: the name is uppercased
+/// first, then each character is folded in with `hash = hash*37 + c`.
+pub(crate) fn hash_name(name: &str) -> u32 {
+    name.to_uppercase()
+        .chars()
+        .fold(0u32, |hash, c| hash.wrapping_mul(37).wrapping_add(c as u32))
+}
+
+/// Computes the Windows `lf` name hint: the first four bytes of the
+/// (uppercased) name, zero-padded if the name is shorter than that.
+pub(crate) fn name_prefix(name: &str) -> [u8; 4] {
+    let mut prefix = [0u8; 4];
+    for (dst, b) in prefix.iter_mut().zip(name.to_uppercase().bytes()) {
+        *dst = b;
+    }
+    prefix
+}
@@ -0,0 +1,47 @@
+use std::io::{Read, Seek};
+
+use binread::{BinReaderExt, BinResult, ReadOptions};
+use chrono::{DateTime, TimeZone, Utc};
+
+/// Seconds between the Windows `FILETIME` epoch (1601-01-01) and the Unix
+/// epoch (1970-01-01).
+const FILETIME_EPOCH_DIFF_SECS: i64 = 11_644_473_600;
+
+/// Parses a Windows `FILETIME` (100-nanosecond intervals since 1601-01-01)
+/// into a UTC timestamp.
+pub(crate) fn parse_timestamp<R: Read + Seek>(
+    reader: &mut R,
+    _ro: &ReadOptions,
+    _: (),
+) -> BinResult<DateTime<Utc>> {
+    let filetime: u64 = reader.read_le()?;
+    let intervals = filetime as i64;
+    let secs = intervals / 10_000_000 - FILETIME_EPOCH_DIFF_SECS;
+    let nanos = (intervals % 10_000_000) * 100;
+    Ok(Utc
+        .timestamp_opt(secs, nanos as u32)
+        .single()
+        .unwrap_or_else(|| Utc.timestamp_opt(0, 0).single().unwrap()))
+}
+
+/// Parses a name that is either (extended) ASCII or UTF-16LE, depending on
+/// `args.0`, reading `options.count` bytes.
+pub(crate) fn parse_string<R: Read + Seek>(
+    reader: &mut R,
+    options: &ReadOptions,
+    args: (bool,),
+) -> BinResult<String> {
+    let count = options.count.expect("parse_string requires a byte count");
+    let is_ascii = args.0;
+
+    if is_ascii {
+        let mut buf = vec![0u8; count];
+        reader.read_exact(&mut buf)?;
+        Ok(buf.iter().map(|&b| b as char).collect())
+    } else {
+        let units: Vec<u16> = (0..count / 2)
+            .map(|_| reader.read_le())
+            .collect::<BinResult<Vec<u16>>>()?;
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
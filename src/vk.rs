@@ -0,0 +1,148 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use binread::derive_binread;
+use binread::BinRead;
+use binread::BinReaderExt;
+use binread::BinResult;
+use binread::ReadOptions;
+use bitflags::bitflags;
+
+use crate::db;
+use crate::util::parse_string;
+use crate::Cell;
+use crate::Hive;
+use crate::Offset;
+
+#[derive(BinRead, Debug)]
+#[br(import(count: usize))]
+pub(crate) struct KeyValueList {
+    #[br(count=count)]
+    pub(crate) key_value_offsets: Vec<Offset>,
+}
+
+fn parse_value_flags<R: Read + Seek>(reader: &mut R, _ro: &ReadOptions, _: ())
+-> BinResult<KeyValueFlags>
+{
+    let raw_value: u16 = reader.read_le()?;
+    Ok(KeyValueFlags::from_bits_truncate(raw_value))
+}
+
+bitflags! {
+    struct KeyValueFlags: u16 {
+        /// The value name is in (extended) ASCII instead of UTF-16LE.
+        const VALUE_COMP_NAME = 0x0001;
+    }
+}
+
+/// A value's decoded `REG_*` data type and payload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryValue {
+    None(Vec<u8>),
+    String(String),
+    ExpandString(String),
+    Binary(Vec<u8>),
+    Dword(u32),
+    DwordBigEndian(u32),
+    Link(String),
+    MultiString(Vec<String>),
+    ResourceList(Vec<u8>),
+    FullResourceDescriptor(Vec<u8>),
+    ResourceRequirementsList(Vec<u8>),
+    Qword(u64),
+}
+
+#[allow(dead_code)]
+#[derive_binread]
+#[br(magic = b"vk")]
+pub struct KeyValue {
+    #[br(temp)]
+    name_length: u16,
+
+    data_length: u32,
+    data_offset: Offset,
+    value_type: u32,
+
+    #[br(parse_with=parse_value_flags)]
+    flags: KeyValueFlags,
+
+    #[br(temp)]
+    spare: u16,
+
+    #[br(   parse_with=parse_string,
+            count=name_length,
+            args(flags.contains(KeyValueFlags::VALUE_COMP_NAME)))]
+    value_name: String,
+}
+
+impl KeyValue {
+    pub fn name(&self) -> &str {
+        if self.value_name.is_empty() {
+            "(default)"
+        } else {
+            &self.value_name
+        }
+    }
+
+    pub fn value_type(&self) -> u32 {
+        self.value_type
+    }
+
+    /// Reads and decodes this value's data, transparently reassembling it
+    /// from a `db` cell if it is larger than `db::MAX_INLINE_VALUE_SIZE` bytes.
+    pub fn value<B>(&self, hive: &mut Hive<B>) -> BinResult<RegistryValue> where B: BinReaderExt {
+        // The high bit of `data_length` marks data stored inline in the
+        // `data_offset` field itself rather than at the cell it points to.
+        let is_inline = self.data_length & 0x8000_0000 != 0;
+        let data_length = self.data_length & 0x7FFF_FFFF;
+
+        let raw = if is_inline {
+            self.data_offset.0.to_le_bytes()[..data_length.min(4) as usize].to_vec()
+        } else if data_length > db::MAX_INLINE_VALUE_SIZE {
+            db::read_big_data(hive, self.data_offset, data_length)?
+        } else {
+            hive.seek(SeekFrom::Start(hive.resolve(self.data_offset)))?;
+            let mut buf = vec![0u8; data_length as usize];
+            hive.read_exact(&mut buf)?;
+            buf
+        };
+
+        Ok(decode(self.value_type, raw))
+    }
+}
+
+fn decode(value_type: u32, raw: Vec<u8>) -> RegistryValue {
+    match value_type {
+        1 => RegistryValue::String(decode_utf16(&raw)),
+        2 => RegistryValue::ExpandString(decode_utf16(&raw)),
+        3 => RegistryValue::Binary(raw),
+        4 => RegistryValue::Dword(u32::from_le_bytes(raw[..4.min(raw.len())].try_into().unwrap_or_default())),
+        5 => RegistryValue::DwordBigEndian(u32::from_be_bytes(raw[..4.min(raw.len())].try_into().unwrap_or_default())),
+        6 => RegistryValue::Link(decode_utf16(&raw)),
+        7 => RegistryValue::MultiString(
+            decode_utf16(&raw)
+                .split('\0')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        8 => RegistryValue::ResourceList(raw),
+        9 => RegistryValue::FullResourceDescriptor(raw),
+        10 => RegistryValue::ResourceRequirementsList(raw),
+        11 => RegistryValue::Qword(u64::from_le_bytes(raw[..8.min(raw.len())].try_into().unwrap_or_default())),
+        _ => RegistryValue::None(raw),
+    }
+}
+
+fn decode_utf16(raw: &[u8]) -> String {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+impl From<Cell<KeyValue, ()>> for KeyValue {
+    fn from(cell: Cell<KeyValue, ()>) -> Self {
+        cell.into_data()
+    }
+}